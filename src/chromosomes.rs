@@ -0,0 +1,163 @@
+//! Multi-chromosome assemblies: every FASTA record gets its own chromosome
+//! cylinder, laid out in parallel along Y, and its own spiral of ORFs.
+
+use std::io::Read;
+
+use bevy::prelude::*;
+use fffx::Fasta;
+
+use crate::coloring::{find_all_orfs_both_strands, TaggedOrf};
+use crate::{Config, Genome};
+
+/// Vertical spacing between chromosome cylinders.
+pub const CHROMOSOME_SPACING: f32 = 3.0;
+
+pub fn chromosome_y_offset(index: usize) -> f32 {
+    index as f32 * CHROMOSOME_SPACING
+}
+
+/// Decodes genome bytes (gzip or plain) into a reader `Fasta` can consume.
+pub fn genome_reader(bytes: &[u8]) -> Box<std::io::BufReader<dyn Read + '_>> {
+    if bytes.len() >= 2 && bytes[0..2] == [0x1f, 0x8b] {
+        let decompressed = flate2::read::GzDecoder::new(bytes);
+        Box::new(std::io::BufReader::new(decompressed))
+    } else {
+        Box::new(std::io::BufReader::new(bytes))
+    }
+}
+
+/// Names of every record in the selected genome, in FASTA order, with a
+/// per-record on/off toggle the user can flip before starting.
+#[derive(Resource, Default)]
+pub struct ChromosomeToggles {
+    pub names: Vec<String>,
+    pub enabled: Vec<bool>,
+}
+
+#[derive(PartialEq, Clone)]
+enum GenomeMarker {
+    Nasonia,
+    Custom(usize),
+}
+
+/// Re-scans record names whenever the selected genome actually changes
+/// (not on every slider tweak), so picking a genome in the menu populates
+/// the chromosome toggle list.
+pub fn scan_chromosome_names(
+    config: Res<Config>,
+    mut toggles: ResMut<ChromosomeToggles>,
+    mut last: Local<Option<GenomeMarker>>,
+) {
+    let marker = match config.genome {
+        Genome::Nasonia => GenomeMarker::Nasonia,
+        Genome::Custom(ref bytes) => GenomeMarker::Custom(bytes.len()),
+    };
+
+    if Some(marker.clone()) == *last {
+        return;
+    }
+    *last = Some(marker);
+
+    if let Genome::Custom(ref bytes) = config.genome {
+        if bytes.is_empty() {
+            toggles.names.clear();
+            toggles.enabled.clear();
+            return;
+        }
+    }
+
+    let names = match config.genome {
+        Genome::Nasonia => read_record_names(crate::NASONIA_GENOME),
+        Genome::Custom(ref bytes) => read_record_names(bytes),
+    };
+
+    toggles.enabled = vec![true; names.len()];
+    toggles.names = names;
+}
+
+fn read_record_names(bytes: &[u8]) -> Vec<String> {
+    let mut buf_reader = genome_reader(bytes);
+    let mut reader = Fasta::from_buffer(&mut buf_reader);
+
+    let mut names = Vec::new();
+    while let Some(Ok(record)) = reader.next() {
+        names.push(String::from_utf8_lossy(&record.id).into_owned());
+    }
+    names
+}
+
+/// Draws the chromosome on/off checkboxes in the `Settings` window.
+pub fn chromosome_toggle_ui(ui: &mut bevy_egui::egui::Ui, toggles: &mut ChromosomeToggles) {
+    if toggles.names.is_empty() {
+        return;
+    }
+
+    ui.collapsing("Chromosomes", |ui| {
+        for (name, enabled) in toggles.names.iter().zip(toggles.enabled.iter_mut()) {
+            ui.checkbox(enabled, name);
+        }
+    });
+}
+
+/// One record's worth of popped-ORF source data, produced by `startup_data`
+/// for every enabled chromosome.
+pub struct ChromosomeData {
+    pub index: usize,
+    pub length: usize,
+    pub orfs: Vec<TaggedOrf>,
+}
+
+/// Reads every record from the genome, spawning a cylinder for each enabled
+/// chromosome and collecting its ORFs. Disabled chromosomes still advance
+/// the reader (so indices stay aligned with `ChromosomeToggles`) but are not
+/// spawned or searched for ORFs.
+pub fn load_chromosomes(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Handle<StandardMaterial>,
+    bytes: &[u8],
+    orf_min: usize,
+    toggles: &ChromosomeToggles,
+) -> Vec<ChromosomeData> {
+    let mut buf_reader = genome_reader(bytes);
+    let mut reader = Fasta::from_buffer(&mut buf_reader);
+
+    let mut chromosomes = Vec::new();
+    let mut index = 0;
+
+    while let Some(Ok(record)) = reader.next() {
+        let Some(seq) = record.sequence else {
+            index += 1;
+            continue;
+        };
+
+        let enabled = toggles.enabled.get(index).copied().unwrap_or(true);
+        let length = seq.len();
+
+        if enabled {
+            let cylinder = meshes.add(Cylinder::new(0.1, length as f32 / 1_000_000.0));
+            commands.spawn((
+                bevy_xpbd_3d::prelude::RigidBody::Static,
+                PbrBundle {
+                    mesh: cylinder,
+                    material: material.clone(),
+                    transform: Transform::from_xyz(0.0, chromosome_y_offset(index), 0.0)
+                        .with_rotation(Quat::from_rotation_z(-std::f32::consts::PI / 2.)),
+                    ..default()
+                },
+                crate::Chromosome(index),
+            ));
+
+            let orfs = find_all_orfs_both_strands(&seq, orf_min);
+            chromosomes.push(ChromosomeData {
+                index,
+                length,
+                orfs,
+            });
+        }
+
+        index += 1;
+    }
+
+    chromosomes
+}
@@ -0,0 +1,158 @@
+//! Reading-frame and strand coloring for ORFs.
+//!
+//! `ffforf::find_all_orfs` only scans the forward strand, so reverse-strand
+//! ORFs are found explicitly by running it again against the
+//! reverse-complement and reflecting the coordinates back onto the original
+//! sequence.
+
+use bevy::prelude::*;
+use ffforf::{find_all_orfs, Orf};
+
+use crate::translate::translate;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// An ORF tagged with the reading frame (0/1/2) and strand it was found on,
+/// plus its translated amino-acid sequence.
+pub struct TaggedOrf {
+    pub orf: Orf,
+    pub frame: u8,
+    pub strand: Strand,
+    pub amino_acids: String,
+}
+
+/// Six-entry palette: one color per (frame, strand) combination.
+const PALETTE: [Color; 6] = [
+    Color::rgb(0.0, 1.0, 1.0),   // forward, frame 0 (cyan, the original default)
+    Color::rgb(0.2, 0.8, 0.2),   // forward, frame 1
+    Color::rgb(0.9, 0.8, 0.1),   // forward, frame 2
+    Color::rgb(0.9, 0.2, 0.2),   // reverse, frame 0
+    Color::rgb(0.7, 0.2, 0.9),   // reverse, frame 1
+    Color::rgb(0.9, 0.5, 0.1),   // reverse, frame 2
+];
+
+pub fn palette_index(frame: u8, strand: Strand) -> usize {
+    let strand_offset = match strand {
+        Strand::Forward => 0,
+        Strand::Reverse => 3,
+    };
+    strand_offset + (frame as usize % 3)
+}
+
+pub fn orf_color(frame: u8, strand: Strand) -> Color {
+    PALETTE[palette_index(frame, strand)]
+}
+
+pub fn legend_label(index: usize) -> &'static str {
+    match index {
+        0 => "Forward, frame 0",
+        1 => "Forward, frame 1",
+        2 => "Forward, frame 2",
+        3 => "Reverse, frame 0",
+        4 => "Reverse, frame 1",
+        5 => "Reverse, frame 2",
+        _ => "",
+    }
+}
+
+/// Which (frame, strand) combinations are currently shown.
+#[derive(Resource)]
+pub struct OrfColorFilter {
+    pub visible: [bool; 6],
+}
+
+impl Default for OrfColorFilter {
+    fn default() -> Self {
+        OrfColorFilter { visible: [true; 6] }
+    }
+}
+
+impl OrfColorFilter {
+    pub fn is_visible(&self, frame: u8, strand: Strand) -> bool {
+        self.visible[palette_index(frame, strand)]
+    }
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' | b'a' => b'T',
+        b'T' | b't' => b'A',
+        b'C' | b'c' => b'G',
+        b'G' | b'g' => b'C',
+        other => other,
+    }
+}
+
+fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|&b| complement(b)).collect()
+}
+
+/// Clamps `start..end` to a valid, ordered range within `seq` before
+/// slicing, in case `ffforf` ever hands back an out-of-bounds or inverted
+/// `Orf`.
+fn bounded_slice(seq: &[u8], start: usize, end: usize) -> &[u8] {
+    let start = start.min(seq.len());
+    let end = end.clamp(start, seq.len());
+    &seq[start..end]
+}
+
+/// Finds ORFs on both strands, tagging each with its reading frame and strand.
+pub fn find_all_orfs_both_strands(seq: &[u8], orf_min: usize) -> Vec<TaggedOrf> {
+    let mut tagged = Vec::new();
+
+    for orf in find_all_orfs(seq, orf_min) {
+        let frame = (orf.start % 3) as u8;
+        let amino_acids = translate(bounded_slice(seq, orf.start, orf.end));
+        tagged.push(TaggedOrf {
+            orf,
+            frame,
+            strand: Strand::Forward,
+            amino_acids,
+        });
+    }
+
+    let rc = reverse_complement(seq);
+    let len = seq.len();
+    for orf in find_all_orfs(&rc, orf_min) {
+        let frame = (orf.start % 3) as u8;
+        let amino_acids = translate(bounded_slice(&rc, orf.start, orf.end));
+        // Reflect the reverse-complement coordinates back onto the original
+        // sequence's coordinate space.
+        let start = len - orf.end;
+        let end = len - orf.start;
+        tagged.push(TaggedOrf {
+            orf: Orf { start, end },
+            frame,
+            strand: Strand::Reverse,
+            amino_acids,
+        });
+    }
+
+    tagged
+}
+
+/// Draws the legend and per-frame/per-strand visibility checkboxes in the
+/// `Settings` window.
+pub fn color_filter_ui(ui: &mut bevy_egui::egui::Ui, filter: &mut OrfColorFilter) {
+    ui.collapsing("ORF colors", |ui| {
+        for (index, visible) in filter.visible.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let color = orf_color(
+                    (index % 3) as u8,
+                    if index < 3 {
+                        Strand::Forward
+                    } else {
+                        Strand::Reverse
+                    },
+                );
+                let [r, g, b, _] = color.as_rgba_u8();
+                ui.colored_label(bevy_egui::egui::Color32::from_rgb(r, g, b), "\u{25A0}");
+                ui.checkbox(visible, legend_label(index));
+            });
+        }
+    });
+}
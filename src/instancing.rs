@@ -0,0 +1,189 @@
+//! GPU-instanced ORF rendering.
+//!
+//! Instead of spawning a `PbrBundle` + rigid body per ORF, this renders the
+//! whole revealed set with a single shared cylinder mesh and a custom
+//! [`Material`] backed by a storage buffer of per-instance data. The spiral
+//! trajectory is computed per-instance in the vertex shader from
+//! `globals.time - spawn_time`, so hundreds of thousands of ORFs can be
+//! shown at once without per-entity physics or aggressive culling.
+
+use bevy::{
+    prelude::*,
+    reflect::TypePath,
+    render::{
+        render_resource::{AsBindGroup, ShaderRef, ShaderType},
+        view::NoFrustumCulling,
+    },
+};
+use bytemuck::{Pod, Zeroable};
+
+use ffforf::Orf;
+
+use crate::Config;
+
+pub const ORF_INSTANCE_SHADER: &str = "shaders/orf_instance.wgsl";
+
+/// Which path is used to get popped ORFs on screen.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum RenderMode {
+    /// One `PbrBundle` + rigid body per ORF (the original behaviour).
+    #[default]
+    Physics,
+    /// A single instanced draw driven by [`InstancedOrfMaterial`].
+    Instanced,
+}
+
+/// Per-instance data uploaded to the storage buffer, one entry per revealed ORF.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, ShaderType)]
+pub struct OrfInstance {
+    pub start: f32,
+    /// Visual cylinder length, normalized the same way as physics mode's
+    /// `size` (`orf_length / config.orf_length_max * 2.0 + 0.1`), not raw
+    /// base pairs — otherwise instances would render hundreds-to-thousands
+    /// of units tall.
+    pub length: f32,
+    pub spawn_time: f32,
+    pub color_frame: f32,
+    /// This chromosome's vertical offset (`chromosome_y_offset`), so
+    /// instanced ORFs stack the same way physics-mode ones do.
+    pub y: f32,
+}
+
+/// Custom material binding the growable storage buffer of [`OrfInstance`] data.
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+pub struct InstancedOrfMaterial {
+    #[storage(0, read_only)]
+    pub instances: Vec<OrfInstance>,
+}
+
+impl Material for InstancedOrfMaterial {
+    fn vertex_shader() -> ShaderRef {
+        ORF_INSTANCE_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        ORF_INSTANCE_SHADER.into()
+    }
+}
+
+/// The single shared entity drawing every instanced ORF, and the handle to its
+/// material so new instances can be appended as ORFs are revealed.
+#[derive(Resource)]
+pub struct InstancedOrfs {
+    pub material: Handle<InstancedOrfMaterial>,
+    pub entity: Entity,
+    /// How many instances were uploaded as of the last `upload_new_instances`
+    /// run, so it can tell whether `push_instances` appended anything new
+    /// this frame instead of re-deriving the storage buffer unconditionally.
+    uploaded_count: usize,
+}
+
+pub struct OrfInstancingPlugin;
+
+impl Plugin for OrfInstancingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<InstancedOrfMaterial>::default())
+            .init_resource::<RenderMode>()
+            .add_systems(
+                Update,
+                upload_new_instances.run_if(resource_exists::<InstancedOrfs>),
+            );
+    }
+}
+
+/// Spawns the single shared cylinder + custom material used for instanced ORFs.
+pub fn spawn_instanced_orf_entity(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<InstancedOrfMaterial>,
+) -> InstancedOrfs {
+    let mesh = meshes.add(Cylinder::new(0.15, 1.0));
+    let material = materials.add(InstancedOrfMaterial {
+        instances: Vec::new(),
+    });
+
+    // Instances are displaced arbitrarily far from the origin by the vertex
+    // shader, but the mesh's AABB is a unit cylinder at the origin — without
+    // this, Bevy frustum-culls the whole draw as soon as the origin itself
+    // leaves view.
+    let entity = commands
+        .spawn((
+            MaterialMeshBundle {
+                mesh,
+                material: material.clone(),
+                ..default()
+            },
+            NoFrustumCulling,
+        ))
+        .id();
+
+    InstancedOrfs {
+        material,
+        entity,
+        uploaded_count: 0,
+    }
+}
+
+/// Appends an instance entry for every ORF popped this frame, mirroring
+/// `pop_orf_from_the_end_spiral_animation`'s physics-mode bookkeeping.
+pub fn push_instances(
+    materials: &mut Assets<InstancedOrfMaterial>,
+    instanced: &InstancedOrfs,
+    config: &Config,
+    orf: &Orf,
+    chromosome_length: usize,
+    y: f32,
+    spawn_time: f32,
+    color_frame: f32,
+) {
+    let Some(material) = materials.get_mut(&instanced.material) else {
+        return;
+    };
+
+    let start = orf.start as f32 - chromosome_length as f32 / 2.0;
+    let orf_length = (orf.end - orf.start) as f32;
+    let length = (orf_length / config.orf_length_max as f32) * 2.0 + 0.1;
+    material.instances.push(OrfInstance {
+        start,
+        length,
+        spawn_time,
+        color_frame,
+        y,
+    });
+}
+
+/// Grows the storage buffer to match however many instances have been queued.
+/// `AsBindGroup` re-derives the GPU buffer whenever the `Vec` contents change
+/// (detected via `Assets::get_mut`'s change-detection), so this only calls
+/// `get_mut` — re-deriving and re-uploading the whole buffer — on frames
+/// where `push_instances` actually appended something, rather than every
+/// frame forever.
+fn upload_new_instances(
+    mut instanced: Option<ResMut<InstancedOrfs>>,
+    mut materials: ResMut<Assets<InstancedOrfMaterial>>,
+) {
+    let Some(instanced) = instanced.as_mut() else {
+        return;
+    };
+    let Some(material) = materials.get(&instanced.material) else {
+        return;
+    };
+
+    let count = material.instances.len();
+    if count == instanced.uploaded_count {
+        return;
+    }
+    instanced.uploaded_count = count;
+    materials.get_mut(&instanced.material);
+}
+
+/// Draws the render-mode radio buttons in the `Settings` window.
+pub fn render_mode_ui(ui: &mut bevy_egui::egui::Ui, render_mode: &mut RenderMode, config: &Config) {
+    let _ = config;
+    ui.horizontal(|ui| {
+        ui.label("Render mode");
+        ui.selectable_value(render_mode, RenderMode::Physics, "Physics");
+        ui.selectable_value(render_mode, RenderMode::Instanced, "Instanced (GPU)");
+    });
+}
@@ -1,4 +1,5 @@
 use bevy::{
+    pbr::ShadowFilteringMethod,
     prelude::*,
     render::{
         render_asset::RenderAssetUsages,
@@ -11,14 +12,45 @@ use bevy_async_task::{AsyncTaskRunner, AsyncTaskStatus};
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 use bevy_xpbd_3d::prelude::*;
 use ffforf::*;
-use fffx::Fasta;
-use rand::prelude::*;
 use rfd::AsyncFileDialog;
 
 use std::collections::VecDeque;
 use std::f32::consts::PI;
 use std::time::Duration;
 
+mod instancing;
+use instancing::{
+    push_instances, render_mode_ui, spawn_instanced_orf_entity, InstancedOrfMaterial,
+    InstancedOrfs, OrfInstancingPlugin, RenderMode,
+};
+
+mod sonify;
+use sonify::{play_orf_tone, sonify_ui, SonifyPlugin, SonifySettings, Tone, VoicePool};
+
+mod persistence;
+use persistence::load_persisted_config;
+
+mod shadows;
+use shadows::{shadow_settings_ui, ShadowQualityPlugin, ShadowSettings};
+
+mod chromosomes;
+use chromosomes::{
+    chromosome_toggle_ui, chromosome_y_offset, load_chromosomes, scan_chromosome_names,
+    ChromosomeToggles,
+};
+
+mod coloring;
+use coloring::{color_filter_ui, orf_color, palette_index, OrfColorFilter, TaggedOrf};
+
+mod translate;
+
+mod picking;
+use picking::{camera_mode_ui, CameraMode, OrfData, PickingPlugin};
+
+pub const NASONIA_GENOME: &[u8] = include_bytes!(
+    "../data/Nasonia_vitripennis.Nvit_psr_1.1.dna.primary_assembly.CM020934.1.fa.gz"
+);
+
 // Enum that will be used as a global state for the game
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum AppState {
@@ -80,14 +112,24 @@ fn main() {
                     ..default()
                 }),
         )
-        .add_systems(Startup, startup)
+        .add_systems(Startup, (startup, load_persisted_config))
         .add_plugins(EguiPlugin)
         // .add_plugins(WorldInspectorPlugin::new())
         .add_plugins(PhysicsPlugins::default())
+        .add_plugins(OrfInstancingPlugin)
+        .add_plugins(SonifyPlugin)
+        .add_plugins(ShadowQualityPlugin)
+        .add_plugins(PickingPlugin)
         .insert_resource(Config::default())
         .insert_resource(Gravity(Vec3::new(0.0, 0.0, 0.0)))
         .insert_resource(SubstepCount(2))
+        .init_resource::<ChromosomeToggles>()
+        .init_resource::<OrfColorFilter>()
         .add_systems(Update, gui.run_if(in_state(AppState::Menu)))
+        .add_systems(
+            Update,
+            scan_chromosome_names.run_if(in_state(AppState::Menu)),
+        )
         .add_systems(OnEnter(AppState::Run), startup_data)
         .add_systems(
             Update,
@@ -105,24 +147,41 @@ fn main() {
 pub fn startup(mut commands: Commands,
 ) {
         // Camera!
-        let e = commands.spawn(Camera3dBundle {
-            transform: Transform::from_xyz(0.0, 6., 26.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
-            ..default()
-        }).id();
+        let e = commands.spawn((
+            Camera3dBundle {
+                transform: Transform::from_xyz(0.0, 6., 26.0).looking_at(Vec3::new(0., 1., 0.), Vec3::Y),
+                ..default()
+            },
+            ShadowFilteringMethod::default(),
+            SpatialListener::new(4.0),
+        )).id();
 }
 
 pub fn gui(
     mut contexts: EguiContexts,
     mut config: ResMut<Config>,
+    mut render_mode: ResMut<RenderMode>,
+    mut sonify_settings: ResMut<SonifySettings>,
+    mut shadow_settings: ResMut<ShadowSettings>,
+    mut chromosome_toggles: ResMut<ChromosomeToggles>,
+    mut color_filter: ResMut<OrfColorFilter>,
+    mut camera_mode: ResMut<CameraMode>,
     mut commands: Commands,
     mut app_state: ResMut<NextState<AppState>>,
     mut task_executor: AsyncTaskRunner<Vec<u8>>,
 ) {
-    egui::Window::new("Settings")        
+    egui::Window::new("Settings")
         .default_width(400.0)
         .pivot(bevy_egui::egui::Align2::CENTER_CENTER)
         .show(contexts.ctx_mut(), |ui| {
 
+        render_mode_ui(ui, &mut render_mode, &config);
+        sonify_ui(ui, &mut sonify_settings);
+        shadow_settings_ui(ui, &mut shadow_settings);
+        chromosome_toggle_ui(ui, &mut chromosome_toggles);
+        color_filter_ui(ui, &mut color_filter);
+        camera_mode_ui(ui, &mut camera_mode);
+
         // Radio button
         ui.horizontal(|ui| {
             ui.radio_value(&mut config.genome, Genome::Nasonia, "Nasonia");
@@ -184,9 +243,15 @@ pub fn gui(
         });
         ui.label("Increases the density of the ORF cloud");
 
+        // Save settings button
+        if ui.button("Save settings").clicked() {
+            persistence::save(&config);
+        }
+
         // Reset button
         if ui.button("Reset").clicked() {
             commands.insert_resource(Config::default());
+            persistence::clear();
         }
 
         if let Genome::Custom(ref data) = config.genome {
@@ -235,10 +300,10 @@ pub fn cull(
 
 #[derive(Resource)]
 pub struct Orfs {
-    orfs: VecDeque<Orf>,
+    orfs: VecDeque<(usize, TaggedOrf)>,
     timer: Timer,
-    chromosome_length: usize,
-    random_list: Vec<usize>,
+    /// Sequence length of each chromosome, indexed by chromosome index.
+    chromosome_lengths: Vec<usize>,
     entities: VecDeque<Entity>,
 }
 
@@ -252,10 +317,17 @@ pub fn pop_orf_from_the_end_spiral_animation(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut instanced_materials: ResMut<Assets<InstancedOrfMaterial>>,
+    instanced: Option<Res<InstancedOrfs>>,
+    render_mode: Res<RenderMode>,
+    mut tones: ResMut<Assets<Tone>>,
+    sonify_settings: Res<SonifySettings>,
+    mut voices: ResMut<VoicePool>,
     mut orfs: ResMut<Orfs>,
     time: Res<Time>,
     mut orf_number: ResMut<OrfNumber>,
     config: Res<Config>,
+    color_filter: Res<OrfColorFilter>,
 ) {
     orfs.timer.tick(time.delta());
 
@@ -266,7 +338,7 @@ pub fn pop_orf_from_the_end_spiral_animation(
         let mut front = false;
 
         for _ in 0..config.orfs_to_pop_per_step {
-            let orf = if front {
+            let popped = if front {
                 // front = !front;
                 orfs.orfs.pop_front()
             } else {
@@ -274,14 +346,53 @@ pub fn pop_orf_from_the_end_spiral_animation(
                 orfs.orfs.pop_back()
             };
 
-            let orf = match orf {
+            let (chromosome, tagged) = match popped {
                 None => return,
-                Some(orf) => orf,
+                Some(popped) => popped,
             };
 
+            if !color_filter.is_visible(tagged.frame, tagged.strand) {
+                continue;
+            }
+
+            let orf = tagged.orf;
+            let chromosome_length = orfs.chromosome_lengths[chromosome];
+            let y = chromosome_y_offset(chromosome);
+
+            let angle = orf_number.0 as f32 * 0.1;
+            orf_number.0 += 1;
+
+            let x = (orf.start as f32 - chromosome_length as f32 / 2.0)
+                / (chromosome_length as f32 / 2.0).max(1.0);
+            play_orf_tone(
+                &mut commands,
+                &mut tones,
+                &sonify_settings,
+                &mut voices,
+                &config,
+                &orf,
+                x,
+            );
+
+            if *render_mode == RenderMode::Instanced {
+                if let Some(instanced) = instanced.as_deref() {
+                    push_instances(
+                        &mut instanced_materials,
+                        instanced,
+                        &config,
+                        &orf,
+                        chromosome_length,
+                        y,
+                        time.elapsed_seconds(),
+                        palette_index(tagged.frame, tagged.strand) as f32,
+                    );
+                }
+                continue;
+            }
+
             let orf_length = orf.end - orf.start;
             let size = (orf_length as f32 / config.orf_length_max as f32) * 2.0 + 0.1;
-            let color = Color::CYAN;
+            let color = orf_color(tagged.frame, tagged.strand);
 
             let cylinder = meshes.add(Cylinder::new(0.15, size));
             let color = materials.add(StandardMaterial {
@@ -291,29 +402,39 @@ pub fn pop_orf_from_the_end_spiral_animation(
 
             // Each orf shoots off in a spiral from the end of the chromosome, based
             // on which number it is in the list determines the proper angle
-            let angle = orf_number.0 as f32 * 0.1;
-            orf_number.0 += 1;
-
             let velocity = Vec3::new(0.0, angle.cos() * 6.0, angle.sin() * 6.0);
 
-            // Place it at the start of the orf on the chromosome (chromosome is centered 0,0,0, length is in orfs.chromosome_length)
+            // Place it at the start of the orf on the chromosome (chromosome is centered at x = 0 at height y, length is in chromosome_length)
             // Because it is centered, those left of the center will be negative in x
-            let x = (orf.start as f32 - orfs.chromosome_length as f32 / 2.0) / 1_000_000.0;
+            let x = (orf.start as f32 - chromosome_length as f32 / 2.0) / 1_000_000.0;
 
             let id = commands
                 .spawn((
                     RigidBody::Dynamic,
-                    // Collider::cylinder(0.1, size),
                     MassPropertiesBundle::new_computed(&Collider::cylinder(0.1, size), 2.5),
+                    // A sensor collider is queryable by `pick_orf`'s raycast
+                    // without generating contact responses, so adding it for
+                    // picking doesn't also make thousands of ORFs collide
+                    // with each other the way a solid collider would.
+                    Collider::cylinder(0.1, size),
+                    Sensor,
                     LinearVelocity(velocity),
                     PbrBundle {
                         mesh: cylinder,
                         material: color,
-                        transform: Transform::from_xyz(x, 0.0, 0.0)
+                        transform: Transform::from_xyz(x, y, 0.0)
                             .with_rotation(Quat::from_rotation_z(-PI / 2.)),
                         ..default()
                     },
                     OrfInSpace,
+                    OrfData {
+                        chromosome,
+                        start: orf.start,
+                        end: orf.end,
+                        frame: tagged.frame,
+                        strand: tagged.strand,
+                        amino_acids: tagged.amino_acids.clone(),
+                    },
                 ))
                 .id();
 
@@ -327,8 +448,13 @@ pub fn startup_data(
     mut meshes: ResMut<Assets<Mesh>>,
     mut images: ResMut<Assets<Image>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    mut instanced_materials: ResMut<Assets<InstancedOrfMaterial>>,
+    chromosome_toggles: Res<ChromosomeToggles>,
     mut config: ResMut<Config>,
 ) {
+    let instanced = spawn_instanced_orf_entity(&mut commands, &mut meshes, &mut instanced_materials);
+    commands.insert_resource(instanced);
+
     // Lights!
     commands.spawn(PointLightBundle {
         point_light: PointLight {
@@ -349,82 +475,52 @@ pub fn startup_data(
         ..default()
     });
 
-    let file_contents = include_bytes!(
-        "../data/Nasonia_vitripennis.Nvit_psr_1.1.dna.primary_assembly.CM020934.1.fa.gz"
-    );
-
-    let file_contents = file_contents.to_vec();
-
     let bytes = match config.genome {
-        Genome::Nasonia => file_contents,
+        Genome::Nasonia => NASONIA_GENOME.to_vec(),
         Genome::Custom(ref bytes) => bytes.clone(),
     };
 
-    // Test if gzip compressed
-    let mut buf_reader: Box<std::io::BufReader<dyn std::io::Read>> = if bytes[0..2] == [0x1f, 0x8b]
-    {
-        // It's gzipped
-        let decompressed = flate2::read::GzDecoder::new(&bytes[..]);
-        Box::new(std::io::BufReader::new(decompressed))
-    } else {
-        Box::new(std::io::BufReader::new(&bytes[..]))
-    };
-
-    let mut reader = Fasta::from_buffer(&mut buf_reader);
-
-    let record = reader.next().expect("record").expect("record");
-    let seq = record.sequence.expect("sequence");
-
-    let sequence_length = seq.len();
+    // Let's pull out all the ORFs for display later... save as a resource right now...
 
-    // Let's add a cylinder to represent the chromosome, where every 100kbp is 1 unit
-    let cylinder = meshes.add(Cylinder::new(0.1, sequence_length as f32 / 1_000_000.0));
+    let orf_min = config.orf_length_min;
 
-    commands.spawn((
-        RigidBody::Static,
-        // Collider::cylinder(0.1, sequence_length as f32 / 1_000_000.0),
-        PbrBundle {
-            mesh: cylinder,
-            material: debug_material.clone(),
-            // Let's place is flat, so it lies along lengthwise like a stick where you see the wide side
-            transform: Transform::from_xyz(0.0, 0.0, 0.0)
-                .with_rotation(Quat::from_rotation_z(-PI / 2.)),
-            ..default()
-        },
-        Chromosome,
-    ));
+    let chromosomes = load_chromosomes(
+        &mut commands,
+        &mut meshes,
+        debug_material.clone(),
+        &bytes,
+        orf_min,
+        &chromosome_toggles,
+    );
 
-    // Let's pull out all the ORFs for display later... save as a resource right now...
+    let mut chromosome_lengths = vec![0usize; chromosomes.iter().map(|c| c.index).max().map_or(0, |m| m + 1)];
+    let mut all_orfs: Vec<(usize, TaggedOrf)> = Vec::new();
 
-    let orf_min = config.orf_length_min;
+    for chromosome in chromosomes {
+        chromosome_lengths[chromosome.index] = chromosome.length;
+        all_orfs.extend(chromosome.orfs.into_iter().map(|orf| (chromosome.index, orf)));
+    }
 
-    let mut all_orfs = find_all_orfs(&seq, orf_min);
-    all_orfs.sort_by(|a, b| a.start.cmp(&b.start));
-    let mut random_list = (0..all_orfs.len()).collect::<Vec<usize>>();
-    let mut rng = rand::thread_rng();
-    random_list.as_mut_slice().shuffle(&mut rng);
+    all_orfs.sort_by(|a, b| a.1.orf.start.cmp(&b.1.orf.start));
 
     // Calc orf lengths so we can get the min and max
-    let mut orf_lengths = all_orfs
+    let orf_lengths = all_orfs
         .iter()
-        .map(|orf| orf.end - orf.start)
+        .map(|(_, tagged)| tagged.orf.end - tagged.orf.start)
         .collect::<Vec<usize>>();
-    let orf_length_max = orf_lengths.iter().max().unwrap();
-    let orf_length_min = orf_lengths.iter().min().unwrap();
+    let orf_length_max = orf_lengths.iter().max().copied().unwrap_or(orf_min);
+    let orf_length_min = orf_lengths.iter().min().copied().unwrap_or(orf_min);
 
-    config.orf_length_max = *orf_length_max;
-    config.orf_length_min = *orf_length_min;
+    config.orf_length_max = orf_length_max;
+    config.orf_length_min = orf_length_min;
 
     let orfs = Orfs {
         orfs: all_orfs.into(),
         timer: Timer::new(Duration::from_secs(2), TimerMode::Once),
-        chromosome_length: sequence_length,
-        random_list,
+        chromosome_lengths,
         entities: VecDeque::new(),
     };
 
-    drop(reader);
-
     commands.insert_resource(orfs);
     commands.insert_resource(OrfNumber(0));
 
@@ -436,9 +532,10 @@ pub fn startup_data(
     config.orf_material = color;
 }
 
-/// A marker component for our shapes so we can query them separately from the ground plane
+/// A marker component for our shapes so we can query them separately from the ground plane,
+/// holding the index of the chromosome it represents.
 #[derive(Component)]
-struct Chromosome;
+struct Chromosome(usize);
 
 /// Creates a colorful test pattern
 fn uv_debug_texture() -> Image {
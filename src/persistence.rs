@@ -0,0 +1,131 @@
+//! Persists the user-tunable fields of [`Config`] across sessions: the
+//! `Settings` window in `gui()` should come up with the last-used values
+//! instead of always resetting to [`Config::default`].
+//!
+//! Custom-uploaded genome bytes are never serialized, only which `Genome`
+//! variant was selected, since the upload has to happen again anyway.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, Genome};
+
+#[derive(Serialize, Deserialize)]
+pub struct PersistedConfig {
+    pub orfs_to_pop_per_step: usize,
+    pub orf_length_min: usize,
+    pub culling: usize,
+    pub genome: PersistedGenome,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+pub enum PersistedGenome {
+    Nasonia,
+    Custom,
+}
+
+impl From<&Config> for PersistedConfig {
+    fn from(config: &Config) -> Self {
+        PersistedConfig {
+            orfs_to_pop_per_step: config.orfs_to_pop_per_step,
+            orf_length_min: config.orf_length_min,
+            culling: config.culling,
+            genome: match config.genome {
+                Genome::Nasonia => PersistedGenome::Nasonia,
+                Genome::Custom(_) => PersistedGenome::Custom,
+            },
+        }
+    }
+}
+
+impl PersistedConfig {
+    /// Applies the persisted fields onto a freshly-defaulted `Config`.
+    /// `Genome::Custom` comes back empty, prompting the user to re-upload.
+    pub fn apply(self, config: &mut Config) {
+        config.orfs_to_pop_per_step = self.orfs_to_pop_per_step;
+        config.orf_length_min = self.orf_length_min;
+        config.culling = self.culling;
+        config.genome = match self.genome {
+            PersistedGenome::Nasonia => Genome::Nasonia,
+            PersistedGenome::Custom => Genome::Custom(Vec::new()),
+        };
+    }
+}
+
+/// Startup system: loads any previously-saved settings into `Config` before
+/// the menu is first shown.
+pub fn load_persisted_config(mut config: ResMut<Config>) {
+    if let Some(persisted) = load() {
+        persisted.apply(&mut config);
+    }
+}
+
+pub fn save(config: &Config) {
+    let persisted = PersistedConfig::from(config);
+    let Ok(json) = serde_json::to_string(&persisted) else {
+        return;
+    };
+    backend::save(&json);
+}
+
+pub fn load() -> Option<PersistedConfig> {
+    let json = backend::load()?;
+    serde_json::from_str(&json).ok()
+}
+
+pub fn clear() {
+    backend::clear();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::fs;
+
+    fn settings_path() -> Option<std::path::PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "", "explode_orfs")?;
+        Some(dirs.config_dir().join("settings.json"))
+    }
+
+    pub fn save(json: &str) {
+        let Some(path) = settings_path() else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(path, json);
+    }
+
+    pub fn load() -> Option<String> {
+        fs::read_to_string(settings_path()?).ok()
+    }
+
+    pub fn clear() {
+        if let Some(path) = settings_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    const STORAGE_KEY: &str = "explode_orfs.settings";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn save(json: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(STORAGE_KEY, json);
+        }
+    }
+
+    pub fn load() -> Option<String> {
+        local_storage()?.get_item(STORAGE_KEY).ok()?
+    }
+
+    pub fn clear() {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(STORAGE_KEY);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Click-to-inspect for flying ORF cylinders, plus a free-look/orbit camera
+//! so the user can navigate the cloud to find something to click.
+
+use bevy::{input::mouse::MouseMotion, prelude::*};
+use bevy_xpbd_3d::prelude::*;
+
+use crate::coloring::Strand;
+use crate::OrfInSpace;
+
+/// Snapshot of the ORF an entity represents, stored alongside it so the
+/// click handler can look up what was actually picked.
+#[derive(Component, Clone)]
+pub struct OrfData {
+    pub chromosome: usize,
+    pub start: usize,
+    pub end: usize,
+    pub frame: u8,
+    pub strand: Strand,
+    pub amino_acids: String,
+}
+
+#[derive(Resource, Default)]
+pub struct Selection(pub Option<Entity>);
+
+#[derive(Resource, Default, Eq, PartialEq)]
+pub enum CameraMode {
+    #[default]
+    Fixed,
+    Orbit,
+}
+
+#[derive(Resource)]
+pub struct OrbitCamera {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        OrbitCamera {
+            yaw: 0.0,
+            pitch: 0.2,
+            distance: 26.0,
+        }
+    }
+}
+
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selection>()
+            .init_resource::<CameraMode>()
+            .init_resource::<OrbitCamera>()
+            .add_systems(
+                Update,
+                (pick_orf, orbit_camera, inspector_panel).run_if(in_state(crate::AppState::Run)),
+            );
+    }
+}
+
+fn pick_orf(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    spatial_query: SpatialQuery,
+    orfs: Query<&OrfData, With<OrfInSpace>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut selection: ResMut<Selection>,
+    highlight: Query<&Handle<StandardMaterial>, With<OrfInSpace>>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get_single() else {
+        return;
+    };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+
+    let hit = spatial_query.cast_ray(
+        ray.origin,
+        ray.direction.into(),
+        1000.0,
+        true,
+        SpatialQueryFilter::default(),
+    );
+
+    // Restore the previously-selected entity's material before applying a
+    // new highlight (or clearing the selection entirely).
+    if let Some(previous) = selection.0.take() {
+        if let Ok(material) = highlight.get(previous) {
+            if let Some(material) = materials.get_mut(material) {
+                material.emissive = Color::BLACK;
+            }
+        }
+    }
+
+    let Some(hit) = hit else { return };
+    if orfs.get(hit.entity).is_err() {
+        return;
+    }
+
+    if let Ok(material) = highlight.get(hit.entity) {
+        if let Some(material) = materials.get_mut(material) {
+            material.emissive = Color::rgb(1.0, 1.0, 1.0);
+        }
+    }
+
+    selection.0 = Some(hit.entity);
+}
+
+/// Draws an egui panel with the selected ORF's details, if any.
+fn inspector_panel(
+    mut contexts: bevy_egui::EguiContexts,
+    selection: Res<Selection>,
+    orfs: Query<&OrfData>,
+) {
+    let Some(entity) = selection.0 else { return };
+    let Ok(orf) = orfs.get(entity) else { return };
+
+    bevy_egui::egui::Window::new("ORF Inspector").show(contexts.ctx_mut(), |ui| {
+        ui.label(format!("Chromosome: {}", orf.chromosome));
+        ui.label(format!("Start: {}", orf.start));
+        ui.label(format!("End: {}", orf.end));
+        ui.label(format!("Length: {}", orf.end - orf.start));
+        ui.label(format!("Frame: {}", orf.frame));
+        ui.label(format!(
+            "Strand: {}",
+            match orf.strand {
+                Strand::Forward => "+",
+                Strand::Reverse => "-",
+            }
+        ));
+        ui.separator();
+        ui.label("Translated sequence:");
+        ui.label(bevy_egui::egui::RichText::new(&orf.amino_acids).monospace());
+    });
+}
+
+/// Toggles between the fixed startup camera and a free-look orbit camera.
+pub fn camera_mode_ui(ui: &mut bevy_egui::egui::Ui, mode: &mut CameraMode) {
+    ui.horizontal(|ui| {
+        ui.label("Camera");
+        ui.selectable_value(mode, CameraMode::Fixed, "Fixed");
+        ui.selectable_value(mode, CameraMode::Orbit, "Orbit");
+    });
+}
+
+fn orbit_camera(
+    mode: Res<CameraMode>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut orbit: ResMut<OrbitCamera>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    if *mode != CameraMode::Orbit {
+        mouse_motion.clear();
+        return;
+    }
+
+    if mouse.pressed(MouseButton::Right) {
+        for motion in mouse_motion.read() {
+            orbit.yaw -= motion.delta.x * 0.005;
+            orbit.pitch = (orbit.pitch - motion.delta.y * 0.005).clamp(-1.5, 1.5);
+        }
+    } else {
+        mouse_motion.clear();
+    }
+
+    let Ok(mut transform) = cameras.get_single_mut() else {
+        return;
+    };
+
+    let rotation = Quat::from_euler(EulerRot::YXZ, orbit.yaw, orbit.pitch, 0.0);
+    transform.translation = rotation * Vec3::new(0.0, 0.0, orbit.distance);
+    transform.look_at(Vec3::ZERO, Vec3::Y);
+}
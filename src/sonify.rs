@@ -0,0 +1,185 @@
+//! Genome sonification: each popped ORF triggers a short tone, pitched by
+//! ORF length and panned by its position along the chromosome.
+//!
+//! There's no OGG encoder available to bake a recorded sample into this
+//! tree, so the tone is synthesized at playback time instead of decoded
+//! from a file — the same `Decodable`-source mechanism Bevy's own
+//! `audio/pitch` example uses, rather than a pitch-shifted sample.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::{
+    audio::{AddAudioSource, Decodable, PlaybackMode, Source},
+    prelude::*,
+};
+use ffforf::Orf;
+
+use crate::Config;
+
+/// A pentatonic scale (major pentatonic, one octave) expressed as
+/// multipliers against `BASE_FREQUENCY`.
+const PENTATONIC_SCALE: [f32; 5] = [1.0, 1.122, 1.26, 1.498, 1.682];
+
+const BASE_FREQUENCY: f32 = 220.0;
+const TONE_DURATION: Duration = Duration::from_millis(220);
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Hard cap on simultaneously playing voices so a step popping dozens of
+/// ORFs at once doesn't clip.
+const MAX_VOICES: usize = 8;
+
+#[derive(Resource)]
+pub struct SonifySettings {
+    pub master_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for SonifySettings {
+    fn default() -> Self {
+        SonifySettings {
+            master_volume: 0.6,
+            muted: false,
+        }
+    }
+}
+
+/// A short, decaying sine tone, synthesized sample-by-sample rather than
+/// decoded from a file.
+#[derive(Asset, TypePath, Clone)]
+pub struct Tone {
+    frequency: f32,
+    samples_remaining: usize,
+    sample_index: usize,
+}
+
+impl Tone {
+    fn new(frequency: f32) -> Self {
+        Tone {
+            frequency,
+            samples_remaining: (SAMPLE_RATE as f32 * TONE_DURATION.as_secs_f32()) as usize,
+            sample_index: 0,
+        }
+    }
+}
+
+impl Iterator for Tone {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.samples_remaining == 0 {
+            return None;
+        }
+        self.samples_remaining -= 1;
+
+        let t = self.sample_index as f32 / SAMPLE_RATE as f32;
+        self.sample_index += 1;
+
+        // Linear fade-out avoids a click at the end of the tone.
+        let total = (SAMPLE_RATE as f32 * TONE_DURATION.as_secs_f32()).max(1.0);
+        let envelope = self.samples_remaining as f32 / total;
+
+        Some((t * self.frequency * std::f32::consts::TAU).sin() * envelope)
+    }
+}
+
+impl Source for Tone {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(TONE_DURATION)
+    }
+}
+
+impl Decodable for Tone {
+    type DecoderItem = f32;
+    type Decoder = Tone;
+
+    fn decoder(&self) -> Self::Decoder {
+        self.clone()
+    }
+}
+
+/// Entities currently playing an ORF-pop voice, oldest first, so the oldest
+/// can be cut off once `MAX_VOICES` is exceeded.
+#[derive(Resource, Default)]
+pub struct VoicePool(pub VecDeque<Entity>);
+
+pub struct SonifyPlugin;
+
+impl Plugin for SonifyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SonifySettings>()
+            .init_resource::<VoicePool>()
+            .add_audio_source::<Tone>();
+    }
+}
+
+/// Maps an ORF's length onto the pentatonic scale between
+/// `config.orf_length_min` and `config.orf_length_max`, and its chromosome
+/// `x` position onto a stereo pan in `-1.0..=1.0`.
+pub fn play_orf_tone(
+    commands: &mut Commands,
+    tones: &mut Assets<Tone>,
+    settings: &SonifySettings,
+    voices: &mut VoicePool,
+    config: &Config,
+    orf: &Orf,
+    x: f32,
+) {
+    if settings.muted || settings.master_volume <= 0.0 {
+        return;
+    }
+
+    let span = (config.orf_length_max.saturating_sub(config.orf_length_min)).max(1) as f32;
+    let t = ((orf.end - orf.start).saturating_sub(config.orf_length_min)) as f32 / span;
+    let note = ((t * PENTATONIC_SCALE.len() as f32) as usize).min(PENTATONIC_SCALE.len() - 1);
+    let frequency = BASE_FREQUENCY * PENTATONIC_SCALE[note];
+
+    let pan = x.clamp(-1.0, 1.0);
+
+    let id = commands
+        .spawn((
+            AudioSourceBundle {
+                source: tones.add(Tone::new(frequency)),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Despawn,
+                    volume: bevy::audio::Volume::new(settings.master_volume),
+                    spatial: true,
+                    ..default()
+                },
+                ..default()
+            },
+            TransformBundle::from_transform(Transform::from_xyz(pan * 4.0, 0.0, 0.0)),
+        ))
+        .id();
+
+    voices.0.push_back(id);
+    if voices.0.len() > MAX_VOICES {
+        if let Some(oldest) = voices.0.pop_front() {
+            commands.entity(oldest).despawn_recursive();
+        }
+    }
+}
+
+/// Draws the master volume slider and mute checkbox in the `Settings` window.
+pub fn sonify_ui(ui: &mut bevy_egui::egui::Ui, settings: &mut SonifySettings) {
+    ui.horizontal(|ui| {
+        ui.label("Master volume");
+        ui.add(bevy_egui::egui::Slider::new(
+            &mut settings.master_volume,
+            0.0..=1.0,
+        ));
+    });
+    ui.checkbox(&mut settings.muted, "Mute");
+}
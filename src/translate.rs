@@ -0,0 +1,36 @@
+//! Translates a nucleotide ORF sequence into its amino-acid sequence using
+//! the standard genetic code.
+
+fn codon_to_amino_acid(codon: &[u8]) -> char {
+    let upper: Vec<u8> = codon.iter().map(u8::to_ascii_uppercase).collect();
+    match &upper[..] {
+        b"TTT" | b"TTC" => 'F',
+        b"TTA" | b"TTG" | b"CTT" | b"CTC" | b"CTA" | b"CTG" => 'L',
+        b"ATT" | b"ATC" | b"ATA" => 'I',
+        b"ATG" => 'M',
+        b"GTT" | b"GTC" | b"GTA" | b"GTG" => 'V',
+        b"TCT" | b"TCC" | b"TCA" | b"TCG" | b"AGT" | b"AGC" => 'S',
+        b"CCT" | b"CCC" | b"CCA" | b"CCG" => 'P',
+        b"ACT" | b"ACC" | b"ACA" | b"ACG" => 'T',
+        b"GCT" | b"GCC" | b"GCA" | b"GCG" => 'A',
+        b"TAT" | b"TAC" => 'Y',
+        b"TAA" | b"TAG" | b"TGA" => '*',
+        b"CAT" | b"CAC" => 'H',
+        b"CAA" | b"CAG" => 'Q',
+        b"AAT" | b"AAC" => 'N',
+        b"AAA" | b"AAG" => 'K',
+        b"GAT" | b"GAC" => 'D',
+        b"GAA" | b"GAG" => 'E',
+        b"TGT" | b"TGC" => 'C',
+        b"TGG" => 'W',
+        b"CGT" | b"CGC" | b"CGA" | b"CGG" | b"AGA" | b"AGG" => 'R',
+        b"GGT" | b"GGC" | b"GGA" | b"GGG" => 'G',
+        _ => 'X',
+    }
+}
+
+/// Translates a nucleotide sequence codon-by-codon, dropping any trailing
+/// partial codon.
+pub fn translate(seq: &[u8]) -> String {
+    seq.chunks_exact(3).map(codon_to_amino_acid).collect()
+}